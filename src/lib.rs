@@ -5,3 +5,6 @@ pub mod raw;
 
 pub use map::{HashMap, HashMapRef, Iter, Keys, ResizeMode, Values};
 pub use seize::{Guard, OwnedGuard};
+
+#[cfg(feature = "aes-hash")]
+pub use raw::utils::random::RandomState;
@@ -170,54 +170,689 @@ impl Counter {
     }
 }
 
-pub mod arch {
-    use std::arch::{asm, x86_64};
-    use std::num::NonZeroU16;
+/// A fast, DoS-resistant default [`BuildHasher`](std::hash::BuildHasher).
+///
+/// [`RandomState`] seeds each hasher from a lazily-initialized, process-global
+/// key combined with a per-instance counter, so that two `HashMap`s created
+/// in the same process still hash the same keys differently. This makes it
+/// impractical for an adversary to choose keys that collide in every map,
+/// without the overhead of a cryptographic hash like SipHash.
+///
+/// On `x86_64` targets with AES-NI, mixing is done with hardware `aesenc`
+/// rounds over the keyed state. Everywhere else, a scalar "folded multiply"
+/// is used instead.
+#[cfg(feature = "aes-hash")]
+pub mod random {
+    #[cfg(all(target_arch = "x86_64", target_feature = "aes"))]
+    use super::arch;
+    use std::hash::{BuildHasher, Hasher};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::OnceLock;
+
+    // Combines two `u64`s via the "folded multiply" primitive: the 128-bit
+    // product of `a` and `b`, with its high and low halves XORed together.
+    #[inline]
+    fn folded_multiply(a: u64, b: u64) -> u64 {
+        let full = (a as u128).wrapping_mul(b as u128);
+        (full as u64) ^ ((full >> 64) as u64)
+    }
+
+    // Returns the process-global keys, seeding them on first use from a mix
+    // of the current time and the address of the `KEYS` static. This doesn't need to be
+    // cryptographically secure, only unpredictable across processes.
+    fn global_keys() -> [u64; 2] {
+        static KEYS: OnceLock<[u64; 2]> = OnceLock::new();
+        *KEYS.get_or_init(|| {
+            let addr = &KEYS as *const _ as u64;
+            let time = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|dur| dur.as_nanos() as u64)
+                .unwrap_or(0);
+
+            [
+                folded_multiply(addr, 0x9E3779B97F4A7C15),
+                folded_multiply(time, 0xC2B2AE3D27D4EB4F),
+            ]
+        })
+    }
+
+    // Incremented once per `RandomState`, so that maps created back-to-back
+    // in the same process still get distinct keys.
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    /// A [`BuildHasher`] that creates [`AesHasher`]s seeded with a random,
+    /// per-instance key.
+    #[derive(Clone)]
+    pub struct RandomState {
+        keys: [u64; 2],
+    }
+
+    impl RandomState {
+        /// Creates a new `RandomState` with a randomly generated key.
+        pub fn new() -> RandomState {
+            let [k0, k1] = global_keys();
+            let counter = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+            // XOR the counter into the key being perturbed (rather than
+            // multiplying by it), so that even the first `RandomState`
+            // (`counter == 0`) diverges from the raw process-global keys.
+            RandomState {
+                keys: [
+                    folded_multiply(k0 ^ counter, k1 | 1),
+                    folded_multiply(k1 ^ counter.rotate_left(32), k0 | 1),
+                ],
+            }
+        }
+    }
+
+    impl Default for RandomState {
+        fn default() -> RandomState {
+            RandomState::new()
+        }
+    }
+
+    impl BuildHasher for RandomState {
+        type Hasher = AesHasher;
+
+        fn build_hasher(&self) -> AesHasher {
+            AesHasher {
+                keys: self.keys,
+                len: 0,
+            }
+        }
+    }
 
-    #[cfg(miri)]
-    pub unsafe fn load_128(src: *mut u128) -> x86_64::__m128i {
-        mem::transmute((*src).to_ne_bytes())
+    /// A [`Hasher`] that mixes input with hardware AES rounds when available,
+    /// falling back to a scalar multiply-and-fold otherwise.
+    pub struct AesHasher {
+        keys: [u64; 2],
+        len: u64,
     }
 
-    #[cfg(not(miri))]
-    pub unsafe fn load_128(src: *mut u128) -> x86_64::__m128i {
-        debug_assert!(src as usize % 16 == 0);
+    impl AesHasher {
+        // Folds a 16-byte block into the keyed state.
+        #[inline]
+        fn write_block(&mut self, block: [u8; 16]) {
+            #[cfg(all(target_arch = "x86_64", target_feature = "aes"))]
+            {
+                self.keys = unsafe { arch::aes::fold(self.keys, block) };
+            }
+
+            #[cfg(not(all(target_arch = "x86_64", target_feature = "aes")))]
+            {
+                let lo = u64::from_ne_bytes(block[..8].try_into().unwrap());
+                let hi = u64::from_ne_bytes(block[8..].try_into().unwrap());
+                self.keys = [
+                    folded_multiply(self.keys[0] ^ lo, self.keys[1] | 1),
+                    folded_multiply(self.keys[1] ^ hi, self.keys[0] | 1),
+                ];
+            }
+        }
+    }
+
+    impl Hasher for AesHasher {
+        fn write(&mut self, mut bytes: &[u8]) {
+            self.len += bytes.len() as u64;
 
-        unsafe {
-            let out: x86_64::__m128i;
-            asm!(
-                concat!("vmovdqa {out}, xmmword ptr [{src}]"),
-                src = in(reg) src,
-                out = out(xmm_reg) out,
-                options(nostack, preserves_flags),
-            );
-            out
+            while bytes.len() >= 16 {
+                let mut block = [0u8; 16];
+                block.copy_from_slice(&bytes[..16]);
+                self.write_block(block);
+                bytes = &bytes[16..];
+            }
+
+            if !bytes.is_empty() {
+                let mut block = [0u8; 16];
+                block[..bytes.len()].copy_from_slice(bytes);
+                self.write_block(block);
+            }
+        }
+
+        fn finish(&self) -> u64 {
+            folded_multiply(self.keys[0] ^ self.len, self.keys[1])
         }
     }
 
-    pub fn match_byte(group: x86_64::__m128i, byte: u8) -> BitIter {
-        unsafe {
-            let cmp = x86_64::_mm_cmpeq_epi8(group, x86_64::_mm_set1_epi8(byte as i8));
-            BitIter(x86_64::_mm_movemask_epi8(cmp) as u16)
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn equal_keys_hash_equal() {
+            let state = RandomState::new();
+
+            let mut a = state.build_hasher();
+            let mut b = state.build_hasher();
+            a.write(b"the quick brown fox jumps over the lazy dog");
+            b.write(b"the quick brown fox jumps over the lazy dog");
+
+            assert_eq!(a.finish(), b.finish());
+        }
+
+        #[test]
+        fn counter_zero_still_perturbs_keys() {
+            // Regression test: the very first `RandomState` in a process
+            // (`counter == 0`) used to have keys identical to the raw
+            // process-global keys, since the counter was combined by
+            // multiplying rather than XORing it in. The global counter can't
+            // be reset from a test, so this repeats the key derivation from
+            // `RandomState::new` with `counter == 0` and fixed stand-in keys.
+            let k0 = 0x1111_1111_1111_1111u64;
+            let k1 = 0x2222_2222_2222_2222u64;
+            let counter = 0u64;
+
+            let keys = [
+                folded_multiply(k0 ^ counter, k1 | 1),
+                folded_multiply(k1 ^ counter.rotate_left(32), k0 | 1),
+            ];
+
+            assert_ne!(keys, [k0, k1]);
         }
     }
+}
+
+// The group-probing primitives are split per architecture below. Every
+// backend exposes the same `load_group`/`match_byte`/`match_empty`/
+// `match_full`/`match_empty_or_deleted`/`BitIter` names and a `GROUP_WIDTH`
+// const, so the probe loop in `map.rs` is written once against whichever
+// backend `cfg` selects for the target; note the concrete group type
+// (`__m128i`, `__m256i`, `uint8x16_t`, or `u64`) and `GROUP_WIDTH` still vary
+// per backend, so the probe loop itself must still be generic over those
+// (e.g. via a small trait or macro), not literally backend-agnostic source.
+pub mod arch {
+    /// The control byte marking an empty slot. The top bit is set, so it is
+    /// distinguishable from a full slot's 7-bit h2 tag in a single movemask;
+    /// every backend's `match_empty_or_deleted`/`match_full` rely on this —
+    /// they test only each byte's top bit rather than comparing against the
+    /// `EMPTY`/`DELETED` values.
+    pub const EMPTY: u8 = 0xFF;
+
+    /// The control byte marking a deleted (tombstone) slot. Like `EMPTY`, the
+    /// top bit is set; unlike `EMPTY`, the low bits are clear.
+    pub const DELETED: u8 = 0x80;
+
+    #[cfg(target_arch = "x86_64")]
+    pub use self::x86_64::*;
+
+    #[cfg(target_arch = "aarch64")]
+    pub use self::aarch64::*;
+
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    pub use self::swar::*;
+
+    #[cfg(target_arch = "x86_64")]
+    mod x86_64 {
+        use std::arch::{asm, x86_64};
+        #[cfg(miri)]
+        use std::mem;
+        use std::num::NonZeroU16;
+
+        pub const GROUP_WIDTH: usize = 16;
+
+        #[cfg(miri)]
+        pub unsafe fn load_group(src: *mut u128) -> x86_64::__m128i {
+            mem::transmute((*src).to_ne_bytes())
+        }
+
+        #[cfg(not(miri))]
+        pub unsafe fn load_group(src: *mut u128) -> x86_64::__m128i {
+            debug_assert!(src as usize % 16 == 0);
+
+            unsafe {
+                let out: x86_64::__m128i;
+                asm!(
+                    concat!("vmovdqa {out}, xmmword ptr [{src}]"),
+                    src = in(reg) src,
+                    out = out(xmm_reg) out,
+                    options(nostack, preserves_flags),
+                );
+                out
+            }
+        }
+
+        pub fn match_byte(group: x86_64::__m128i, byte: u8) -> BitIter {
+            unsafe {
+                let cmp = x86_64::_mm_cmpeq_epi8(group, x86_64::_mm_set1_epi8(byte as i8));
+                BitIter(x86_64::_mm_movemask_epi8(cmp) as u16)
+            }
+        }
+
+        pub fn match_empty_or_deleted(group: x86_64::__m128i) -> BitIter {
+            unsafe { BitIter(x86_64::_mm_movemask_epi8(group) as u16) }
+        }
+
+        pub fn match_full(group: x86_64::__m128i) -> BitIter {
+            unsafe { BitIter(!x86_64::_mm_movemask_epi8(group) as u16) }
+        }
+
+        pub fn match_empty(group: x86_64::__m128i) -> BitIter {
+            match_byte(group, super::EMPTY)
+        }
+
+        pub struct BitIter(u16);
+
+        impl BitIter {
+            pub fn any_set(self) -> bool {
+                self.0 != 0
+            }
+        }
+
+        impl Iterator for BitIter {
+            type Item = usize;
+
+            #[inline]
+            fn next(&mut self) -> Option<usize> {
+                let bit = NonZeroU16::new(self.0)?.trailing_zeros() as usize;
+                self.0 = self.0 & (self.0 - 1);
+                Some(bit)
+            }
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+            use super::super::{DELETED, EMPTY};
+
+            fn group(bytes: [u8; 16]) -> x86_64::__m128i {
+                unsafe { x86_64::_mm_loadu_si128(bytes.as_ptr() as *const x86_64::__m128i) }
+            }
+
+            #[test]
+            fn match_byte_finds_all_positions() {
+                let bytes = [1, 2, 1, 3, 1, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 1];
+                let positions: Vec<usize> = match_byte(group(bytes), 1).collect();
+                assert_eq!(positions, vec![0, 2, 4, 15]);
+            }
+
+            #[test]
+            fn sentinel_matches_agree_with_control_convention() {
+                let bytes = [
+                    EMPTY, 1, DELETED, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, EMPTY,
+                ];
+                let g = group(bytes);
+
+                let empty_or_deleted: Vec<usize> = match_empty_or_deleted(g).collect();
+                assert_eq!(empty_or_deleted, vec![0, 2, 15]);
+
+                let full: Vec<usize> = match_full(g).collect();
+                assert_eq!(full, vec![1, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]);
+
+                let empty: Vec<usize> = match_empty(g).collect();
+                assert_eq!(empty, vec![0, 15]);
+            }
+        }
+
+        /// Opt-in 32-byte group backend, used in place of the 16-byte SSE2
+        /// groups above when AVX2 is available. A 32-byte group covers twice
+        /// as many control bytes as an SSE2 group in a single load and compare,
+        /// halving the number of group iterations the probe loop needs for
+        /// large tables.
+        ///
+        /// Unlike the SSE2 backend, AVX2 isn't guaranteed to be present on a
+        /// stock `x86_64` build, so this module is always compiled in and
+        /// dispatched at runtime: check [`is_available`] once (the probe
+        /// loop would do this when it picks a group width for the table),
+        /// then call the functions below, which are sound to call only once
+        /// that check has passed.
+        pub mod avx2 {
+            use std::arch::x86_64;
+            #[cfg(miri)]
+            use std::mem;
+            use std::num::NonZeroU32;
+
+            pub const GROUP_WIDTH: usize = 32;
+
+            /// Returns whether the AVX2 backend can be used on this CPU.
+            /// The result is cached by `std` after the first call, so
+            /// callers are free to check this on every probe.
+            #[cfg(not(miri))]
+            pub fn is_available() -> bool {
+                std::is_x86_feature_detected!("avx2")
+            }
+
+            #[cfg(miri)]
+            pub fn is_available() -> bool {
+                false
+            }
+
+            #[cfg(miri)]
+            #[target_feature(enable = "avx2")]
+            pub unsafe fn load_group(src: *mut [u8; 32]) -> x86_64::__m256i {
+                unsafe { mem::transmute(*src) }
+            }
 
-    pub struct BitIter(u16);
+            #[cfg(not(miri))]
+            #[target_feature(enable = "avx2")]
+            pub unsafe fn load_group(src: *mut [u8; 32]) -> x86_64::__m256i {
+                debug_assert!(src as usize % 32 == 0);
+                unsafe { x86_64::_mm256_load_si256(src as *const x86_64::__m256i) }
+            }
 
-    impl BitIter {
-        pub fn any_set(self) -> bool {
-            self.0 != 0
+            #[target_feature(enable = "avx2")]
+            pub unsafe fn match_byte(group: x86_64::__m256i, byte: u8) -> BitIter {
+                let cmp = x86_64::_mm256_cmpeq_epi8(group, x86_64::_mm256_set1_epi8(byte as i8));
+                BitIter(x86_64::_mm256_movemask_epi8(cmp) as u32)
+            }
+
+            #[target_feature(enable = "avx2")]
+            pub unsafe fn match_empty_or_deleted(group: x86_64::__m256i) -> BitIter {
+                BitIter(x86_64::_mm256_movemask_epi8(group) as u32)
+            }
+
+            #[target_feature(enable = "avx2")]
+            pub unsafe fn match_full(group: x86_64::__m256i) -> BitIter {
+                BitIter(!x86_64::_mm256_movemask_epi8(group) as u32)
+            }
+
+            #[target_feature(enable = "avx2")]
+            pub unsafe fn match_empty(group: x86_64::__m256i) -> BitIter {
+                unsafe { match_byte(group, super::super::EMPTY) }
+            }
+
+            pub struct BitIter(u32);
+
+            impl BitIter {
+                pub fn any_set(self) -> bool {
+                    self.0 != 0
+                }
+            }
+
+            impl Iterator for BitIter {
+                type Item = usize;
+
+                #[inline]
+                fn next(&mut self) -> Option<usize> {
+                    let bit = NonZeroU32::new(self.0)?.trailing_zeros() as usize;
+                    self.0 = self.0 & (self.0 - 1);
+                    Some(bit)
+                }
+            }
+
+            #[cfg(test)]
+            mod tests {
+                use super::super::super::{DELETED, EMPTY};
+                use super::*;
+
+                fn group(bytes: [u8; 32]) -> x86_64::__m256i {
+                    unsafe { x86_64::_mm256_loadu_si256(bytes.as_ptr() as *const x86_64::__m256i) }
+                }
+
+                #[test]
+                fn match_byte_finds_all_positions() {
+                    if !is_available() {
+                        return;
+                    }
+                    let mut bytes = [0u8; 32];
+                    for &pos in &[0, 2, 4, 31] {
+                        bytes[pos] = 1;
+                    }
+                    let positions: Vec<usize> = unsafe { match_byte(group(bytes), 1) }.collect();
+                    assert_eq!(positions, vec![0, 2, 4, 31]);
+                }
+
+                #[test]
+                fn sentinel_matches_agree_with_control_convention() {
+                    if !is_available() {
+                        return;
+                    }
+                    let mut bytes = [1u8; 32];
+                    bytes[0] = EMPTY;
+                    bytes[2] = DELETED;
+                    bytes[31] = EMPTY;
+                    let g = group(bytes);
+
+                    let empty_or_deleted: Vec<usize> = unsafe { match_empty_or_deleted(g) }.collect();
+                    assert_eq!(empty_or_deleted, vec![0, 2, 31]);
+
+                    let full: Vec<usize> = unsafe { match_full(g) }.collect();
+                    assert_eq!(full, vec![1, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30]);
+
+                    let empty: Vec<usize> = unsafe { match_empty(g) }.collect();
+                    assert_eq!(empty, vec![0, 31]);
+                }
+            }
+        }
+
+        /// Hardware-accelerated mixing for [`super::super::random::AesHasher`].
+        #[cfg(all(feature = "aes-hash", target_feature = "aes"))]
+        pub mod aes {
+            use super::x86_64;
+
+            // Folds a 16-byte block into a pair of keys, one `aesenc` round per
+            // lane. Two lanes are kept so the round's latency can be hidden
+            // behind the next block's load on long inputs.
+            #[inline]
+            pub unsafe fn fold(keys: [u64; 2], block: [u8; 16]) -> [u64; 2] {
+                unsafe {
+                    let block = x86_64::_mm_loadu_si128(block.as_ptr() as *const x86_64::__m128i);
+                    let state = [
+                        x86_64::_mm_set_epi64x(0, keys[0] as i64),
+                        x86_64::_mm_set_epi64x(0, keys[1] as i64),
+                    ];
+
+                    let state = [
+                        x86_64::_mm_aesenc_si128(state[0], block),
+                        x86_64::_mm_aesenc_si128(state[1], block),
+                    ];
+
+                    // One extra round mixes the two lanes' final state together
+                    // before extracting the low 64 bits of each.
+                    let mixed = x86_64::_mm_aesenc_si128(state[0], state[1]);
+
+                    [
+                        x86_64::_mm_cvtsi128_si64(mixed) as u64,
+                        x86_64::_mm_cvtsi128_si64(state[1]) as u64,
+                    ]
+                }
+            }
         }
     }
 
-    impl Iterator for BitIter {
-        type Item = usize;
+    // NEON backend for aarch64 (Apple Silicon, ARM servers). NEON has no
+    // direct equivalent of `_mm_movemask_epi8`, so the 16-lane comparison
+    // mask is reduced to a bitmask by ANDing each lane with a distinct power
+    // of two and horizontally summing the two 8-lane halves.
+    #[cfg(target_arch = "aarch64")]
+    mod aarch64 {
+        use std::arch::aarch64::{self, uint8x16_t};
+        #[cfg(miri)]
+        use std::mem;
+        use std::num::NonZeroU16;
+
+        pub const GROUP_WIDTH: usize = 16;
+
+        #[cfg(miri)]
+        pub unsafe fn load_group(src: *mut u128) -> uint8x16_t {
+            mem::transmute((*src).to_ne_bytes())
+        }
+
+        #[cfg(not(miri))]
+        pub unsafe fn load_group(src: *mut u128) -> uint8x16_t {
+            debug_assert!(src as usize % 16 == 0);
+            unsafe { aarch64::vld1q_u8(src as *const u8) }
+        }
+
+        // Lane `i` holds `1 << i` (mod 8), so that ANDing it with a `0xFF`/`0x00`
+        // comparison mask and summing each 8-lane half yields that half's
+        // movemask byte, since at most one bit can be set per lane.
+        const BIT_MASK: [u8; 16] = [
+            1, 2, 4, 8, 16, 32, 64, 128, 1, 2, 4, 8, 16, 32, 64, 128,
+        ];
+
+        pub fn match_byte(group: uint8x16_t, byte: u8) -> BitIter {
+            unsafe { BitIter(movemask(aarch64::vceqq_u8(group, aarch64::vdupq_n_u8(byte)))) }
+        }
+
+        // Thresholding at `0x80` picks out the sentinels' shared top bit.
+        pub fn match_empty_or_deleted(group: uint8x16_t) -> BitIter {
+            unsafe { BitIter(movemask(aarch64::vcgeq_u8(group, aarch64::vdupq_n_u8(0x80)))) }
+        }
+
+        pub fn match_full(group: uint8x16_t) -> BitIter {
+            unsafe { BitIter(!movemask(aarch64::vcgeq_u8(group, aarch64::vdupq_n_u8(0x80)))) }
+        }
 
+        pub fn match_empty(group: uint8x16_t) -> BitIter {
+            match_byte(group, super::EMPTY)
+        }
+
+        // Reduces a 16-lane `0xFF`/`0x00` comparison mask to a 16-bit
+        // bitmask: AND each lane with a distinct power of two, then
+        // horizontally sum each 8-lane half into one movemask byte.
         #[inline]
-        fn next(&mut self) -> Option<usize> {
-            let bit = NonZeroU16::new(self.0)?.trailing_zeros() as usize;
-            self.0 = self.0 & (self.0 - 1);
-            Some(bit)
+        unsafe fn movemask(cmp: uint8x16_t) -> u16 {
+            unsafe {
+                let masked = aarch64::vandq_u8(cmp, aarch64::vld1q_u8(BIT_MASK.as_ptr()));
+                let lo = aarch64::vaddv_u8(aarch64::vget_low_u8(masked));
+                let hi = aarch64::vaddv_u8(aarch64::vget_high_u8(masked));
+                (hi as u16) << 8 | lo as u16
+            }
+        }
+
+        pub struct BitIter(u16);
+
+        impl BitIter {
+            pub fn any_set(self) -> bool {
+                self.0 != 0
+            }
+        }
+
+        impl Iterator for BitIter {
+            type Item = usize;
+
+            #[inline]
+            fn next(&mut self) -> Option<usize> {
+                let bit = NonZeroU16::new(self.0)?.trailing_zeros() as usize;
+                self.0 = self.0 & (self.0 - 1);
+                Some(bit)
+            }
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+            use super::super::{DELETED, EMPTY};
+
+            fn group(bytes: [u8; 16]) -> uint8x16_t {
+                unsafe { aarch64::vld1q_u8(bytes.as_ptr()) }
+            }
+
+            #[test]
+            fn match_byte_finds_all_positions() {
+                let bytes = [1, 2, 1, 3, 1, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 1];
+                let positions: Vec<usize> = match_byte(group(bytes), 1).collect();
+                assert_eq!(positions, vec![0, 2, 4, 15]);
+            }
+
+            #[test]
+            fn sentinel_matches_agree_with_control_convention() {
+                let bytes = [
+                    EMPTY, 1, DELETED, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, EMPTY,
+                ];
+                let g = group(bytes);
+
+                let empty_or_deleted: Vec<usize> = match_empty_or_deleted(g).collect();
+                assert_eq!(empty_or_deleted, vec![0, 2, 15]);
+
+                let full: Vec<usize> = match_full(g).collect();
+                assert_eq!(full, vec![1, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]);
+
+                let empty: Vec<usize> = match_empty(g).collect();
+                assert_eq!(empty, vec![0, 15]);
+            }
+        }
+    }
+
+    // Portable "SIMD within a register" fallback for targets with no control-
+    // byte vector instructions (wasm, 32-bit, etc). A group is 8 control
+    // bytes packed into a `u64` rather than 16 packed into a vector register,
+    // so `GROUP_WIDTH` is halved here relative to the vector backends.
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    mod swar {
+        use std::num::NonZeroU64;
+
+        pub const GROUP_WIDTH: usize = 8;
+
+        const LSB: u64 = 0x0101_0101_0101_0101;
+        const MSB: u64 = 0x8080_8080_8080_8080;
+
+        pub unsafe fn load_group(src: *mut u64) -> u64 {
+            debug_assert!(src as usize % 8 == 0);
+            unsafe { src.read() }
+        }
+
+        pub fn match_byte(group: u64, byte: u8) -> BitIter {
+            // XOR the byte being searched for into every lane; matching
+            // lanes become zero. A zero byte `x` satisfies
+            // `(x - 1) & !x & 0x80 != 0`, since borrowing from the zero byte
+            // sets its top bit. The borrow carries into the next byte up, so
+            // a byte whose xored value is `0x01` directly above a match is
+            // reported as well, so a set bit marks a candidate rather than a
+            // certain match. Such false positives only occur above a real match.
+            let xored = group ^ (byte as u64 * LSB);
+            BitIter(xored.wrapping_sub(LSB) & !xored & MSB)
+        }
+
+        pub fn match_empty_or_deleted(group: u64) -> BitIter {
+            BitIter(group & MSB)
+        }
+
+        pub fn match_full(group: u64) -> BitIter {
+            BitIter(!group & MSB)
+        }
+
+        pub fn match_empty(group: u64) -> BitIter {
+            match_byte(group, super::EMPTY)
+        }
+
+        pub struct BitIter(u64);
+
+        impl BitIter {
+            pub fn any_set(self) -> bool {
+                self.0 != 0
+            }
+        }
+
+        impl Iterator for BitIter {
+            type Item = usize;
+
+            #[inline]
+            fn next(&mut self) -> Option<usize> {
+                let bit = NonZeroU64::new(self.0)?.trailing_zeros() as usize >> 3;
+                self.0 &= self.0 - 1;
+                Some(bit)
+            }
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+            use super::super::{DELETED, EMPTY};
+
+            fn group(bytes: [u8; 8]) -> u64 {
+                u64::from_ne_bytes(bytes)
+            }
+
+            #[test]
+            fn match_byte_finds_all_positions() {
+                let positions: Vec<usize> =
+                    match_byte(group([1, 2, 1, 3, 1, 4, 5, 1]), 1).collect();
+                assert_eq!(positions, vec![0, 2, 4, 7]);
+            }
+
+            #[test]
+            fn sentinel_matches_agree_with_control_convention() {
+                let g = group([EMPTY, 1, DELETED, 2, 3, 4, 5, EMPTY]);
+
+                let empty_or_deleted: Vec<usize> = match_empty_or_deleted(g).collect();
+                assert_eq!(empty_or_deleted, vec![0, 2, 7]);
+
+                let full: Vec<usize> = match_full(g).collect();
+                assert_eq!(full, vec![1, 3, 4, 5, 6]);
+
+                let empty: Vec<usize> = match_empty(g).collect();
+                assert_eq!(empty, vec![0, 7]);
+            }
         }
     }
 }